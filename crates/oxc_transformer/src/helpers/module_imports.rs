@@ -1,6 +1,8 @@
 use std::cell::RefCell;
 
 use indexmap::IndexMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+
 use oxc_allocator::{Allocator, Vec};
 use oxc_ast::{ast::*, AstBuilder, NONE};
 use oxc_semantic::ReferenceFlags;
@@ -10,7 +12,7 @@ use oxc_traverse::TraverseCtx;
 
 pub struct ImportSpecifier<'a> {
     imported: Atom<'a>,
-    local: Option<Atom<'a>>, // Not used in `require`
+    local: Option<Atom<'a>>, // Bound identifier name; defaults to `imported` if `None`
     symbol_id: SymbolId,
 }
 
@@ -25,6 +27,7 @@ pub enum ImportKind {
     Import,
     ImportDefault,
     Require,
+    Namespace,
 }
 
 #[derive(Hash, Eq)]
@@ -70,6 +73,17 @@ impl<'a> ModuleImports<'a> {
             .or_insert(vec![import]);
     }
 
+    /// Add `import * as local from 'source'`
+    ///
+    /// A module can have at most one namespace binding per source, so repeated
+    /// calls for the same source reuse the first specifier.
+    pub fn add_namespace(&self, source: Atom<'a>, import: ImportSpecifier<'a>) {
+        self.imports
+            .borrow_mut()
+            .entry(ImportType::new(ImportKind::Namespace, source))
+            .or_insert(vec![import]);
+    }
+
     /// Add `import { named_import } from 'source'`
     pub fn add_import(&self, source: Atom<'a>, import: ImportSpecifier<'a>) {
         self.imports
@@ -92,14 +106,121 @@ impl<'a> ModuleImports<'a> {
         }
     }
 
-    pub fn get_import_statements(&self, ctx: &mut TraverseCtx<'a>) -> Vec<'a, Statement<'a>> {
-        self.ast.vec_from_iter(self.imports.borrow_mut().drain(..).map(|(import_type, names)| {
+    /// Build the hoisted import/require statements, alongside any `(symbol_id,
+    /// new_local)` renames a local-name collision forced.
+    ///
+    /// A rename only rewrites the specifier's own `local`; callers must use the
+    /// returned renames to patch any `IdentifierReference` nodes already built
+    /// for that `symbol_id` elsewhere in the program, since this runs after
+    /// every other transform has already built them.
+    pub fn get_import_statements(
+        &self,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> (Vec<'a, Statement<'a>>, std::vec::Vec<(SymbolId, Atom<'a>)>) {
+        let mut entries: std::vec::Vec<_> = self.imports.borrow_mut().drain(..).collect();
+        for (import_type, names) in &mut entries {
+            if import_type.kind == ImportKind::Import {
+                *names = Self::prune_unused_specifiers(std::mem::take(names), ctx);
+            }
+        }
+        entries.retain(|(_, names)| !names.is_empty());
+
+        let mut claimed_locals = Self::seed_claimed_locals(ctx);
+        let mut renames = std::vec::Vec::new();
+        for (_, names) in &mut entries {
+            renames.extend(Self::resolve_local_collisions(names, &mut claimed_locals, ctx));
+        }
+
+        let statements = self.ast.vec_from_iter(entries.into_iter().map(|(import_type, names)| {
             match import_type.kind {
                 ImportKind::Import => self.get_named_import(import_type.source, names),
                 ImportKind::Require => self.get_require(import_type.source, names, ctx),
                 ImportKind::ImportDefault => self.get_default_import(import_type.source, names),
+                ImportKind::Namespace => self.get_namespace_import(import_type.source, names),
             }
-        }))
+        }));
+        (statements, renames)
+    }
+
+    /// Seed the set of local names already claimed in the root scope.
+    fn seed_claimed_locals(ctx: &TraverseCtx<'a>) -> FxHashMap<Atom<'a>, SymbolId> {
+        let scopes = ctx.scopes();
+        scopes
+            .get_bindings(scopes.root_scope_id())
+            .iter()
+            .map(|(name, &symbol_id)| (Atom::from(name.as_str()), symbol_id))
+            .collect()
+    }
+
+    /// Rename specifiers whose intended local name is already claimed by a
+    /// different symbol, returning the `(symbol_id, new_local)` pairs renamed.
+    fn resolve_local_collisions(
+        names: &mut std::vec::Vec<ImportSpecifier<'a>>,
+        claimed_locals: &mut FxHashMap<Atom<'a>, SymbolId>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> std::vec::Vec<(SymbolId, Atom<'a>)> {
+        Self::claim_or_rename(names, claimed_locals, |local| ctx.generate_uid_name(local))
+    }
+
+    /// Pure decision logic behind [`Self::resolve_local_collisions`]; takes the
+    /// fresh-name generator as a closure so it can be unit tested without a
+    /// `TraverseCtx`.
+    fn claim_or_rename(
+        names: &mut std::vec::Vec<ImportSpecifier<'a>>,
+        claimed_locals: &mut FxHashMap<Atom<'a>, SymbolId>,
+        mut generate_unique_name: impl FnMut(&Atom<'a>) -> Atom<'a>,
+    ) -> std::vec::Vec<(SymbolId, Atom<'a>)> {
+        let mut renames = std::vec::Vec::new();
+        for name in names {
+            let local = name.local.clone().unwrap_or_else(|| name.imported.clone());
+            match claimed_locals.get(&local) {
+                Some(&existing_symbol_id) if existing_symbol_id != name.symbol_id => {
+                    let local = generate_unique_name(&local);
+                    claimed_locals.insert(local.clone(), name.symbol_id);
+                    renames.push((name.symbol_id, local.clone()));
+                    name.local = Some(local);
+                }
+                Some(_) => {}
+                None => {
+                    claimed_locals.insert(local, name.symbol_id);
+                }
+            }
+        }
+        renames
+    }
+
+    /// Drop named-import specifiers whose local binding has no remaining references.
+    fn prune_unused_specifiers(
+        names: std::vec::Vec<ImportSpecifier<'a>>,
+        ctx: &TraverseCtx<'a>,
+    ) -> std::vec::Vec<ImportSpecifier<'a>> {
+        Self::filter_referenced(names, |symbol_id| {
+            ctx.symbols().get_resolved_references(symbol_id).next().is_some()
+        })
+    }
+
+    /// Keep only specifiers whose `symbol_id` is reported as referenced.
+    ///
+    /// Only `ImportKind::Import` goes through this: a default/namespace/require
+    /// binding is commonly registered purely for its module's load side effect,
+    /// so pruning those on an unreferenced local would silently drop the
+    /// side effect along with the statement.
+    fn filter_referenced(
+        names: std::vec::Vec<ImportSpecifier<'a>>,
+        is_referenced: impl Fn(SymbolId) -> bool,
+    ) -> std::vec::Vec<ImportSpecifier<'a>> {
+        names.into_iter().filter(|name| is_referenced(name.symbol_id)).collect()
+    }
+
+    /// Build `import('source')` as an expression.
+    ///
+    /// Unlike the other `ImportKind`s, a dynamic import is evaluated and placed
+    /// inline at its use site rather than hoisted to a top-level declaration, so
+    /// it is built directly and never enters the `imports` map merged with static
+    /// `Import`/`Require` entries for the same source.
+    pub fn get_dynamic_import(&self, source: Atom<'a>) -> Expression<'a> {
+        let source = self.ast.expression_string_literal(SPAN, source);
+        self.ast.expression_import(SPAN, source, self.ast.vec(), NONE)
     }
 
     fn get_named_import(
@@ -107,14 +228,20 @@ impl<'a> ModuleImports<'a> {
         source: Atom<'a>,
         names: std::vec::Vec<ImportSpecifier<'a>>,
     ) -> Statement<'a> {
-        let specifiers = self.ast.vec_from_iter(names.into_iter().map(|name| {
-            let local = name.local.unwrap_or_else(|| name.imported.clone());
-            ImportDeclarationSpecifier::ImportSpecifier(self.ast.alloc_import_specifier(
+        let mut seen = FxHashSet::default();
+        let specifiers = self.ast.vec_from_iter(names.into_iter().filter_map(|name| {
+            let local = name.local.clone().unwrap_or_else(|| name.imported.clone());
+            // Keep only the first specifier for a given `(imported, local)` pair, so a
+            // binding recorded twice for the same source doesn't emit `import { x, x }`.
+            if !seen.insert((name.imported.clone(), local.clone())) {
+                return None;
+            }
+            Some(ImportDeclarationSpecifier::ImportSpecifier(self.ast.alloc_import_specifier(
                 SPAN,
                 ModuleExportName::IdentifierName(IdentifierName::new(SPAN, name.imported)),
                 BindingIdentifier::new_with_symbol_id(SPAN, local, name.symbol_id),
                 ImportOrExportKind::Value,
-            ))
+            )))
         }));
         let import_stmt = self.ast.module_declaration_import_declaration(
             SPAN,
@@ -132,10 +259,35 @@ impl<'a> ModuleImports<'a> {
         names: std::vec::Vec<ImportSpecifier<'a>>,
     ) -> Statement<'a> {
         let specifiers = self.ast.vec_from_iter(names.into_iter().map(|name| {
+            let local = name.local.unwrap_or(name.imported);
             ImportDeclarationSpecifier::ImportDefaultSpecifier(
                 self.ast.alloc_import_default_specifier(
                     SPAN,
-                    BindingIdentifier::new_with_symbol_id(SPAN, name.imported, name.symbol_id),
+                    BindingIdentifier::new_with_symbol_id(SPAN, local, name.symbol_id),
+                ),
+            )
+        }));
+        let import_stmt = self.ast.module_declaration_import_declaration(
+            SPAN,
+            Some(specifiers),
+            StringLiteral::new(SPAN, source),
+            NONE,
+            ImportOrExportKind::Value,
+        );
+        self.ast.statement_module_declaration(import_stmt)
+    }
+
+    fn get_namespace_import(
+        &self,
+        source: Atom<'a>,
+        names: std::vec::Vec<ImportSpecifier<'a>>,
+    ) -> Statement<'a> {
+        let specifiers = self.ast.vec_from_iter(names.into_iter().map(|name| {
+            let local = name.local.unwrap_or(name.imported);
+            ImportDeclarationSpecifier::ImportNamespaceSpecifier(
+                self.ast.alloc_import_namespace_specifier(
+                    SPAN,
+                    BindingIdentifier::new_with_symbol_id(SPAN, local, name.symbol_id),
                 ),
             )
         }));
@@ -165,14 +317,36 @@ impl<'a> ModuleImports<'a> {
             let arg = Argument::from(self.ast.expression_string_literal(SPAN, source));
             self.ast.vec1(arg)
         };
-        let name = names.into_iter().next().unwrap();
-        let id = {
-            let ident = BindingIdentifier::new_with_symbol_id(SPAN, name.imported, name.symbol_id);
+        let id = if names.len() == 1 {
+            let name = names.into_iter().next().unwrap();
+            let local = name.local.unwrap_or(name.imported);
+            let ident = BindingIdentifier::new_with_symbol_id(SPAN, local, name.symbol_id);
             self.ast.binding_pattern(
                 self.ast.binding_pattern_kind_from_binding_identifier(ident),
                 NONE,
                 false,
             )
+        } else {
+            // `var { a, b: c } = require('source')`
+            let properties = self.ast.vec_from_iter(names.into_iter().map(|name| {
+                let local = name.local.unwrap_or_else(|| name.imported.clone());
+                let shorthand = local == name.imported;
+                let key = PropertyKey::StaticIdentifier(
+                    self.ast.alloc_identifier_name(SPAN, name.imported),
+                );
+                let ident = BindingIdentifier::new_with_symbol_id(SPAN, local, name.symbol_id);
+                let value = self.ast.binding_pattern(
+                    self.ast.binding_pattern_kind_from_binding_identifier(ident),
+                    NONE,
+                    false,
+                );
+                self.ast.binding_property(SPAN, key, value, shorthand, false)
+            }));
+            self.ast.binding_pattern(
+                self.ast.binding_pattern_kind_object_pattern(SPAN, properties, NONE),
+                NONE,
+                false,
+            )
         };
         let decl = {
             let init = self.ast.expression_call(SPAN, callee, NONE, args, false);
@@ -183,3 +357,82 @@ impl<'a> ModuleImports<'a> {
         self.ast.statement_declaration(var_decl)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use rustc_hash::FxHashMap;
+
+    use super::{ImportSpecifier, ModuleImports, SymbolId};
+
+    fn specifier(imported: &'static str, symbol_id: u32) -> ImportSpecifier<'static> {
+        ImportSpecifier::new(imported.into(), None, SymbolId::new(symbol_id))
+    }
+
+    /// `_2`, `_3`, ... suffix generator standing in for `ctx.generate_uid_name`.
+    fn suffix_generator() -> impl FnMut(&super::Atom<'static>) -> super::Atom<'static> {
+        let mut next = 2;
+        move |local| {
+            let name = format!("{local}_{next}");
+            next += 1;
+            name.into()
+        }
+    }
+
+    #[test]
+    fn claim_or_rename_renames_colliding_local_only() {
+        let mut names = vec![specifier("foo", 0), specifier("foo", 1), specifier("bar", 2)];
+        let mut claimed = FxHashMap::default();
+
+        let renames = ModuleImports::claim_or_rename(&mut names, &mut claimed, suffix_generator());
+
+        assert_eq!(renames, vec![(SymbolId::new(1), "foo_2".into())]);
+        assert_eq!(names[0].local, None);
+        assert_eq!(names[1].local, Some("foo_2".into()));
+        assert_eq!(names[2].local, None);
+    }
+
+    #[test]
+    fn claim_or_rename_is_noop_for_repeat_calls_with_same_symbol() {
+        let mut names = vec![specifier("foo", 0), specifier("foo", 0)];
+        let mut claimed = FxHashMap::default();
+
+        let renames = ModuleImports::claim_or_rename(&mut names, &mut claimed, suffix_generator());
+
+        assert!(renames.is_empty());
+        assert!(names.iter().all(|name| name.local.is_none()));
+    }
+
+    #[test]
+    fn claim_or_rename_respects_names_already_claimed_in_scope() {
+        let mut names = vec![specifier("foo", 0)];
+        let mut claimed = FxHashMap::default();
+        claimed.insert("foo".into(), SymbolId::new(99));
+
+        let renames = ModuleImports::claim_or_rename(&mut names, &mut claimed, suffix_generator());
+
+        assert_eq!(renames, vec![(SymbolId::new(0), "foo_2".into())]);
+        assert_eq!(names[0].local, Some("foo_2".into()));
+    }
+
+    #[test]
+    fn filter_referenced_drops_unreferenced_specifiers_only() {
+        let names = vec![specifier("used", 0), specifier("unused", 1), specifier("alsoUsed", 2)];
+        let referenced = [SymbolId::new(0), SymbolId::new(2)];
+
+        let kept =
+            ModuleImports::filter_referenced(names, |symbol_id| referenced.contains(&symbol_id));
+
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].imported, "used");
+        assert_eq!(kept[1].imported, "alsoUsed");
+    }
+
+    #[test]
+    fn filter_referenced_drops_all_when_none_referenced() {
+        let names = vec![specifier("a", 0), specifier("b", 1)];
+
+        let kept = ModuleImports::filter_referenced(names, |_| false);
+
+        assert!(kept.is_empty());
+    }
+}